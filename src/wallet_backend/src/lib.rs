@@ -3,18 +3,54 @@ use ic_cdk::{init, query, storage, update};
 use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 
-// Define the structure for an Account with a unique ID and a balance.
+// A 32-byte subaccount, following the ICP ledger model where a full account
+// is a Principal (here, an owner string) plus a subaccount. The all-zeros
+// subaccount is the default/"main" account for an owner.
+type Subaccount = [u8; 32];
+
+const DEFAULT_SUBACCOUNT: Subaccount = [0u8; 32];
+
+// Define the structure for an Account, identified by an owner plus a subaccount.
 #[derive(CandidType, Deserialize, Serialize, Clone, Default)]
 struct Account {
-    id: String,       // Unique identifier for the account.
-    balance: u64,     // Account balance in tokens.
+    owner: String,          // The principal (or, today, bare string id) that owns this account.
+    subaccount: Subaccount, // Distinguishes multiple balances held by the same owner.
+    balance: u64,           // Account balance in tokens.
+    counter: u64,           // Monotonically increasing spend counter; advances once per successful `transfer` out (not on `transfer_from` or `burn`).
+}
+
+// A single entry in the ledger's append-only event log.
+// Every balance-changing operation records one of these so clients can
+// reconstruct history without replaying every call.
+#[derive(CandidType, Deserialize, Serialize, Clone)]
+enum Event {
+    Minted {
+        to: String,
+        amount: u64,
+        time: u64,
+    },
+    Transferred {
+        from: String,
+        to: String,
+        amount: u64,
+        time: u64,
+    },
+    Burned {
+        from: String,
+        amount: u64,
+        time: u64,
+    },
 }
 
 // Define the structure for a Ledger to manage accounts and total token supply.
 #[derive(CandidType, Deserialize, Serialize, Default)]
 struct Ledger {
-    accounts: HashMap<String, Account>, // A mapping of account IDs to Account details.
-    total_supply: u64,                  // Total supply of tokens in the ledger.
+    accounts: HashMap<(String, Subaccount), Account>, // A mapping of (owner, subaccount) to Account details.
+    total_supply: u64,                                // Total supply of tokens in the ledger.
+    events: Vec<Event>,                               // Append-only log of minted/transferred/burned events.
+    allowances: HashMap<(String, String), u64>,       // (owner, spender) -> amount the spender may draw via transfer_from.
+    fee: u64,                                         // Flat fee deducted from every transfer, credited to `fee_collector`.
+    fee_collector: String,                            // Owner whose default account receives collected fees.
 }
 
 // Initialization function for the smart contract.
@@ -35,54 +71,454 @@ fn save_ledger(ledger: &Ledger) {
     storage::stable_save((ledger,)).unwrap();
 }
 
+// Turn a caller-supplied, optional subaccount into the 32-byte form stored
+// internally, defaulting to the all-zeros subaccount when none is given.
+// Rejects malformed input with an error instead of trapping the call.
+fn to_subaccount(subaccount: Option<Vec<u8>>) -> Result<Subaccount, String> {
+    match subaccount {
+        Some(bytes) => {
+            if bytes.len() != 32 {
+                return Err("subaccount must be exactly 32 bytes".to_string());
+            }
+            let mut array = DEFAULT_SUBACCOUNT;
+            array.copy_from_slice(&bytes);
+            Ok(array)
+        }
+        None => Ok(DEFAULT_SUBACCOUNT),
+    }
+}
+
+// Render bytes as a lowercase hex string.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// The textual account identifier used in the event log: just the owner for
+// the default subaccount (matching today's bare ids), or "owner.hex" when a
+// non-default subaccount is in play.
+fn account_id(owner: &str, subaccount: &Subaccount) -> String {
+    if *subaccount == DEFAULT_SUBACCOUNT {
+        owner.to_string()
+    } else {
+        format!("{}.{}", owner, hex_encode(subaccount))
+    }
+}
+
 // Mint new tokens and add them to a specified account.
 // If the account does not exist, it is created.
 #[update]
-fn mint(account_id: String, amount: u64) {
+fn mint(owner: String, subaccount: Option<Vec<u8>>, amount: u64) -> Result<(), String> {
     let mut ledger = load_ledger();
-    let account = ledger.accounts.entry(account_id.clone()).or_insert(Account {
-        id: account_id,  // Initialize a new account if it doesn't exist.
-        balance: 0,
-    });
+    let subaccount = to_subaccount(subaccount)?;
+    let account = ledger
+        .accounts
+        .entry((owner.clone(), subaccount))
+        .or_insert(Account {
+            owner: owner.clone(), // Initialize a new account if it doesn't exist.
+            subaccount,
+            balance: 0,
+            counter: 0,
+        });
     account.balance += amount;          // Add the minted amount to the account balance.
     ledger.total_supply += amount;      // Increase the total supply by the minted amount.
+    ledger.events.push(Event::Minted {
+        to: account_id(&owner, &subaccount),
+        amount,
+        time: ic_cdk::api::time(),
+    });
     save_ledger(&ledger);               // Save the updated ledger to stable storage.
+    Ok(())
 }
 
 // Transfer tokens from one account to another.
 // Returns an error if the sender does not have sufficient balance or the account does not exist.
+// `expected_counter` must match the sender's current spend counter; this rejects replayed or
+// reordered transfers built from a stale counter. Only the sender's counter ever advances.
 #[update]
-fn transfer(from: String, to: String, amount: u64) -> Result<(), String> {
+fn transfer(
+    from: String,
+    from_subaccount: Option<Vec<u8>>,
+    to: String,
+    to_subaccount: Option<Vec<u8>>,
+    amount: u64,
+    expected_counter: u64,
+) -> Result<(), String> {
     let mut ledger = load_ledger();
+    let from_subaccount = to_subaccount(from_subaccount)?;
+    let to_subaccount = to_subaccount(to_subaccount)?;
+
+    transfer_inner(
+        &mut ledger,
+        &ic_cdk::caller().to_string(),
+        from,
+        from_subaccount,
+        to,
+        to_subaccount,
+        amount,
+        expected_counter,
+        ic_cdk::api::time(),
+    )?;
+
+    save_ledger(&ledger); // Save the updated ledger to stable storage.
+    Ok(())
+}
+
+// The logic behind `transfer`, taking the caller's identity and the current time as plain
+// arguments instead of reading them from the IC runtime, so it can be unit tested directly.
+fn transfer_inner(
+    ledger: &mut Ledger,
+    caller: &str,
+    from: String,
+    from_subaccount: Subaccount,
+    to: String,
+    to_subaccount: Subaccount,
+    amount: u64,
+    expected_counter: u64,
+    now: u64,
+) -> Result<(), String> {
+    if caller != from {
+        return Err("Caller does not own the sender account".to_string());
+    }
 
     // Get the sender's account and ensure it exists.
-    let from_account = ledger.accounts.get_mut(&from).ok_or("Sender account not found")?;
+    let from_account = ledger
+        .accounts
+        .get_mut(&(from.clone(), from_subaccount))
+        .ok_or("Sender account not found")?;
 
-    // Check if the sender has sufficient balance.
-    if from_account.balance < amount {
+    // Check if the sender has sufficient balance to cover the amount plus the transfer fee.
+    let fee = ledger.fee;
+    let total = amount.checked_add(fee).ok_or("Amount overflow")?;
+    if from_account.balance < total {
         return Err("Insufficient balance".to_string());
     }
 
-    // Deduct the amount from the sender's balance.
-    from_account.balance -= amount;
+    // Reject stale or replayed transfers; the counter must match exactly.
+    if expected_counter != from_account.counter {
+        return Err("Stale or invalid counter".to_string());
+    }
+
+    // Deduct the amount and fee from the sender's balance and advance its spend counter.
+    from_account.balance -= total;
+    from_account.counter += 1;
 
     // Add the amount to the recipient's balance.
-    let to_account = ledger.accounts.entry(to.clone()).or_insert(Account {
-        id: to,          // Initialize a new account for the recipient if it doesn't exist.
-        balance: 0,
+    let to_account = ledger
+        .accounts
+        .entry((to.clone(), to_subaccount))
+        .or_insert(Account {
+            owner: to.clone(), // Initialize a new account for the recipient if it doesn't exist.
+            subaccount: to_subaccount,
+            balance: 0,
+            counter: 0,
+        });
+    to_account.balance += amount;
+
+    credit_fee(ledger, fee);
+
+    ledger.events.push(Event::Transferred {
+        from: account_id(&from, &from_subaccount),
+        to: account_id(&to, &to_subaccount),
+        amount,
+        time: now,
+    });
+
+    Ok(())
+}
+
+// Credit a transfer fee to the fee collector's default account, if one is configured.
+// Shared by `transfer` and `transfer_from` so the fee is applied identically by both.
+fn credit_fee(ledger: &mut Ledger, fee: u64) {
+    if fee == 0 || ledger.fee_collector.is_empty() {
+        return;
+    }
+    let fee_collector = ledger.fee_collector.clone();
+    let collector_account = ledger
+        .accounts
+        .entry((fee_collector.clone(), DEFAULT_SUBACCOUNT))
+        .or_insert(Account {
+            owner: fee_collector,
+            subaccount: DEFAULT_SUBACCOUNT,
+            balance: 0,
+            counter: 0,
+        });
+    collector_account.balance += fee;
+}
+
+// Burn tokens out of an account, permanently shrinking the total supply.
+//
+// Ownership is proven one of two ways: for a principal-style `owner` (e.g. one created
+// by `mint`), the caller's own principal must equal `owner`. For an HD-derived owner (one
+// returned by `create_account`/`create_sub_accounts`), no principal will ever equal the
+// derived id, so the caller instead supplies `proof`, the `(mnemonic, index)` pair that
+// derives to it — knowledge of the mnemonic stands in for caller identity there.
+#[update]
+fn burn(
+    owner: String,
+    subaccount: Option<Vec<u8>>,
+    amount: u64,
+    proof: Option<(String, u32)>,
+) -> Result<(), String> {
+    let mut ledger = load_ledger();
+    let subaccount = to_subaccount(subaccount)?;
+
+    burn_inner(
+        &mut ledger,
+        &ic_cdk::caller().to_string(),
+        owner,
+        subaccount,
+        amount,
+        proof,
+        ic_cdk::api::time(),
+    )?;
+
+    save_ledger(&ledger);
+    Ok(())
+}
+
+// The logic behind `burn`, taking the caller's identity and the current time as plain
+// arguments instead of reading them from the IC runtime, so it can be unit tested directly.
+fn burn_inner(
+    ledger: &mut Ledger,
+    caller: &str,
+    owner: String,
+    subaccount: Subaccount,
+    amount: u64,
+    proof: Option<(String, u32)>,
+    now: u64,
+) -> Result<(), String> {
+    let authorized = match &proof {
+        Some((mnemonic, index)) => derive_account_id(mnemonic, *index) == owner,
+        None => caller == owner,
+    };
+    if !authorized {
+        return Err("Caller is not authorized to burn this account".to_string());
+    }
+
+    let account = ledger
+        .accounts
+        .get_mut(&(owner.clone(), subaccount))
+        .ok_or("Account not found")?;
+    if account.balance < amount {
+        return Err("Insufficient balance".to_string());
+    }
+    account.balance -= amount;
+    ledger.total_supply -= amount;
+
+    ledger.events.push(Event::Burned {
+        from: account_id(&owner, &subaccount),
+        amount,
+        time: now,
     });
+
+    Ok(())
+}
+
+// Set the flat transfer fee and the account that collects it. Restricted to canister
+// controllers, since the fee is a monetary policy lever rather than a per-user setting.
+#[update]
+fn set_fee(fee: u64, fee_collector: String) -> Result<(), String> {
+    if !ic_cdk::api::is_controller(&ic_cdk::caller()) {
+        return Err("Caller is not a controller".to_string());
+    }
+    let mut ledger = load_ledger();
+    ledger.fee = fee;
+    ledger.fee_collector = fee_collector;
+    save_ledger(&ledger);
+    Ok(())
+}
+
+// Draw fresh randomness from the management canister and render it as a hex seed,
+// standing in for a full BIP39 mnemonic phrase when the caller doesn't supply one.
+async fn generate_mnemonic() -> String {
+    let (bytes,) = ic_cdk::api::management_canister::main::raw_rand()
+        .await
+        .expect("raw_rand failed");
+    hex_encode(&bytes)
+}
+
+// Deterministically derive an account id from a mnemonic and an index, the same
+// (seed, index) pair always yielding the same id.
+//
+// Hashed with a fixed-algorithm 64-bit FNV-1a rather than `std::collections::hash_map::
+// DefaultHasher`: DefaultHasher's algorithm is explicitly unspecified and may change across
+// Rust releases, which would silently re-derive every HD account to a different id and
+// orphan any funds sent to it.
+fn derive_account_id(mnemonic: &str, index: u32) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in mnemonic.as_bytes().iter().chain(index.to_le_bytes().iter()) {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    format!("acct_{:016x}", hash)
+}
+
+// Create a zero-balance account at `index` under `mnemonic`, generating a fresh
+// mnemonic when none is supplied. Replaces ad-hoc "account is created on first
+// mint" ids with addresses that are reproducible from the same (mnemonic, index).
+// Returns `(account_id, mnemonic)` so a caller who didn't supply a mnemonic can
+// still recover it afterwards and later derive sub-accounts from it.
+#[update]
+async fn create_account(mnemonic: Option<String>, index: u32) -> (String, String) {
+    let mnemonic = match mnemonic {
+        Some(mnemonic) => mnemonic,
+        None => generate_mnemonic().await,
+    };
+    let id = derive_account_id(&mnemonic, index);
+
+    let mut ledger = load_ledger();
+    ledger
+        .accounts
+        .entry((id.clone(), DEFAULT_SUBACCOUNT))
+        .or_insert(Account {
+            owner: id.clone(),
+            subaccount: DEFAULT_SUBACCOUNT,
+            balance: 0,
+            counter: 0,
+        });
+    save_ledger(&ledger);
+    (id, mnemonic)
+}
+
+// Batch-derive a contiguous range of `count` sub-accounts starting at `base_index`,
+// all under the given `mnemonic` (as returned by `create_account`), returning their
+// ids in order.
+#[update]
+fn create_sub_accounts(mnemonic: String, base_index: u32, count: u32) -> Vec<String> {
+    let mut ledger = load_ledger();
+    let mut ids = Vec::with_capacity(count as usize);
+    for offset in 0..count {
+        let id = derive_account_id(&mnemonic, base_index + offset);
+        ledger
+            .accounts
+            .entry((id.clone(), DEFAULT_SUBACCOUNT))
+            .or_insert(Account {
+                owner: id.clone(),
+                subaccount: DEFAULT_SUBACCOUNT,
+                balance: 0,
+                counter: 0,
+            });
+        ids.push(id);
+    }
+    save_ledger(&ledger);
+    ids
+}
+
+// Approve a spender to draw up to `amount` from the caller's default account via `transfer_from`.
+// A later call overwrites the previous allowance rather than adding to it.
+#[update]
+fn approve(spender: String, amount: u64) {
+    let mut ledger = load_ledger();
+    let owner = ic_cdk::caller().to_string();
+    ledger.allowances.insert((owner, spender), amount);
+    save_ledger(&ledger);
+}
+
+// Transfer tokens out of `from`'s default account on behalf of its owner, debiting the
+// allowance the caller was previously granted via `approve`. Deducts the same flat transfer
+// fee `transfer` does. Fails if the balance, the remaining allowance, or the fee-inclusive
+// total is insufficient.
+#[update]
+fn transfer_from(from: String, to: String, amount: u64) -> Result<(), String> {
+    let spender = ic_cdk::caller().to_string();
+    let mut ledger = load_ledger();
+
+    let allowance = ledger
+        .allowances
+        .get(&(from.clone(), spender.clone()))
+        .copied()
+        .unwrap_or(0);
+    if allowance < amount {
+        return Err("Insufficient allowance".to_string());
+    }
+
+    // Check if the sender has sufficient balance to cover the amount plus the transfer fee.
+    // The fee applies the same way here as it does in `transfer`, so it can't be evaded by
+    // routing value through an allowance instead. Only `amount` is debited from the allowance.
+    let fee = ledger.fee;
+    let total = amount.checked_add(fee).ok_or("Amount overflow")?;
+
+    let from_account = ledger
+        .accounts
+        .get_mut(&(from.clone(), DEFAULT_SUBACCOUNT))
+        .ok_or("Sender account not found")?;
+    if from_account.balance < total {
+        return Err("Insufficient balance".to_string());
+    }
+    from_account.balance -= total;
+
+    let to_account = ledger
+        .accounts
+        .entry((to.clone(), DEFAULT_SUBACCOUNT))
+        .or_insert(Account {
+            owner: to.clone(), // Initialize a new account for the recipient if it doesn't exist.
+            subaccount: DEFAULT_SUBACCOUNT,
+            balance: 0,
+            counter: 0,
+        });
     to_account.balance += amount;
 
-    save_ledger(&ledger); // Save the updated ledger to stable storage.
+    credit_fee(&mut ledger, fee);
+
+    ledger
+        .allowances
+        .insert((from.clone(), spender), allowance - amount);
+
+    ledger.events.push(Event::Transferred {
+        from: account_id(&from, &DEFAULT_SUBACCOUNT),
+        to: account_id(&to, &DEFAULT_SUBACCOUNT),
+        amount,
+        time: ic_cdk::api::time(),
+    });
+
+    save_ledger(&ledger);
     Ok(())
 }
 
 // Query the balance of a specific account.
 // Returns 0 if the account does not exist.
 #[query]
-fn balance_of(account_id: String) -> u64 {
+fn balance_of(account_id: String, subaccount: Option<Vec<u8>>) -> Result<u64, String> {
+    let ledger = load_ledger();
+    let subaccount = to_subaccount(subaccount)?;
+    Ok(ledger
+        .accounts
+        .get(&(account_id, subaccount))
+        .map_or(0, |account| account.balance))
+}
+
+// Query every subaccount balance held by a given owner.
+#[query]
+fn accounts_of(owner: String) -> Vec<(Vec<u8>, u64)> {
     let ledger = load_ledger();
-    ledger.accounts.get(&account_id).map_or(0, |account| account.balance)
+    ledger
+        .accounts
+        .values()
+        .filter(|account| account.owner == owner)
+        .map(|account| (account.subaccount.to_vec(), account.balance))
+        .collect()
+}
+
+// Query the current spend counter of an account, identified by its textual id.
+// Returns 0 for an account that does not exist, since it has never spent.
+#[query]
+fn counter_of(id: String) -> u64 {
+    let ledger = load_ledger();
+    ledger
+        .accounts
+        .values()
+        .find(|account| account_id(&account.owner, &account.subaccount) == id)
+        .map_or(0, |account| account.counter)
+}
+
+// Query how much `spender` is still allowed to draw from `owner` via `transfer_from`.
+#[query]
+fn allowance(owner: String, spender: String) -> u64 {
+    let ledger = load_ledger();
+    ledger.allowances.get(&(owner, spender)).copied().unwrap_or(0)
 }
 
 // Query the total supply of tokens in the ledger.
@@ -91,3 +527,249 @@ fn total_supply() -> u64 {
     let ledger = load_ledger();
     ledger.total_supply
 }
+
+// Query a page of the event log, in chronological order.
+// `start` is the index of the first event to return and `length` caps how
+// many are returned; out-of-range inputs are clamped rather than erroring.
+#[query]
+fn get_transactions(start: u64, length: u64) -> Vec<Event> {
+    let ledger = load_ledger();
+    let start = usize::try_from(start).unwrap_or(usize::MAX);
+    if start >= ledger.events.len() {
+        return Vec::new();
+    }
+    let length = usize::try_from(length).unwrap_or(usize::MAX);
+    let end = start.saturating_add(length).min(ledger.events.len());
+    ledger.events[start..end].to_vec()
+}
+
+// Query every event that touched a given account, either as sender or recipient.
+// `account_id` is the textual id produced by `account_id()` above.
+#[query]
+fn get_account_history(account_id: String) -> Vec<Event> {
+    let ledger = load_ledger();
+    ledger
+        .events
+        .iter()
+        .filter(|event| match event {
+            Event::Minted { to, .. } => *to == account_id,
+            Event::Transferred { from, to, .. } => *from == account_id || *to == account_id,
+            Event::Burned { from, .. } => *from == account_id,
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(owner: &str, subaccount: Subaccount, balance: u64, counter: u64) -> Account {
+        Account {
+            owner: owner.to_string(),
+            subaccount,
+            balance,
+            counter,
+        }
+    }
+
+    #[test]
+    fn transfer_rejects_a_caller_that_does_not_own_the_sender_account() {
+        let mut ledger = Ledger::default();
+        ledger
+            .accounts
+            .insert(("alice".into(), DEFAULT_SUBACCOUNT), account("alice", DEFAULT_SUBACCOUNT, 100, 0));
+
+        let result = transfer_inner(
+            &mut ledger,
+            "mallory",
+            "alice".into(),
+            DEFAULT_SUBACCOUNT,
+            "bob".into(),
+            DEFAULT_SUBACCOUNT,
+            10,
+            0,
+            0,
+        );
+
+        assert_eq!(result, Err("Caller does not own the sender account".to_string()));
+        assert_eq!(ledger.accounts[&("alice".to_string(), DEFAULT_SUBACCOUNT)].balance, 100);
+    }
+
+    #[test]
+    fn transfer_rejects_a_stale_counter() {
+        let mut ledger = Ledger::default();
+        ledger
+            .accounts
+            .insert(("alice".into(), DEFAULT_SUBACCOUNT), account("alice", DEFAULT_SUBACCOUNT, 100, 1));
+
+        let result = transfer_inner(
+            &mut ledger,
+            "alice",
+            "alice".into(),
+            DEFAULT_SUBACCOUNT,
+            "bob".into(),
+            DEFAULT_SUBACCOUNT,
+            10,
+            0, // stale: the account's counter has already advanced to 1
+            0,
+        );
+
+        assert_eq!(result, Err("Stale or invalid counter".to_string()));
+        assert_eq!(ledger.accounts[&("alice".to_string(), DEFAULT_SUBACCOUNT)].balance, 100);
+        assert_eq!(ledger.accounts[&("alice".to_string(), DEFAULT_SUBACCOUNT)].counter, 1);
+    }
+
+    #[test]
+    fn transfer_accepts_a_matching_counter_and_advances_it_exactly_once() {
+        let mut ledger = Ledger::default();
+        ledger
+            .accounts
+            .insert(("alice".into(), DEFAULT_SUBACCOUNT), account("alice", DEFAULT_SUBACCOUNT, 100, 0));
+
+        transfer_inner(
+            &mut ledger,
+            "alice",
+            "alice".into(),
+            DEFAULT_SUBACCOUNT,
+            "bob".into(),
+            DEFAULT_SUBACCOUNT,
+            10,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(ledger.accounts[&("alice".to_string(), DEFAULT_SUBACCOUNT)].balance, 90);
+        assert_eq!(ledger.accounts[&("alice".to_string(), DEFAULT_SUBACCOUNT)].counter, 1);
+        assert_eq!(ledger.accounts[&("bob".to_string(), DEFAULT_SUBACCOUNT)].balance, 10);
+    }
+
+    #[test]
+    fn transfer_deducts_the_fee_and_credits_the_fee_collector() {
+        let mut ledger = Ledger::default();
+        ledger.fee = 2;
+        ledger.fee_collector = "collector".into();
+        ledger
+            .accounts
+            .insert(("alice".into(), DEFAULT_SUBACCOUNT), account("alice", DEFAULT_SUBACCOUNT, 100, 0));
+
+        transfer_inner(
+            &mut ledger,
+            "alice",
+            "alice".into(),
+            DEFAULT_SUBACCOUNT,
+            "bob".into(),
+            DEFAULT_SUBACCOUNT,
+            10,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(ledger.accounts[&("alice".to_string(), DEFAULT_SUBACCOUNT)].balance, 88);
+        assert_eq!(ledger.accounts[&("bob".to_string(), DEFAULT_SUBACCOUNT)].balance, 10);
+        assert_eq!(ledger.accounts[&("collector".to_string(), DEFAULT_SUBACCOUNT)].balance, 2);
+    }
+
+    #[test]
+    fn transfer_rejects_an_amount_plus_fee_overflow_instead_of_wrapping() {
+        let mut ledger = Ledger::default();
+        ledger.fee = 1;
+        ledger
+            .accounts
+            .insert(("alice".into(), DEFAULT_SUBACCOUNT), account("alice", DEFAULT_SUBACCOUNT, u64::MAX, 0));
+
+        let result = transfer_inner(
+            &mut ledger,
+            "alice",
+            "alice".into(),
+            DEFAULT_SUBACCOUNT,
+            "bob".into(),
+            DEFAULT_SUBACCOUNT,
+            u64::MAX,
+            0,
+            0,
+        );
+
+        assert_eq!(result, Err("Amount overflow".to_string()));
+        assert_eq!(ledger.accounts[&("alice".to_string(), DEFAULT_SUBACCOUNT)].balance, u64::MAX);
+        assert!(!ledger.accounts.contains_key(&("bob".to_string(), DEFAULT_SUBACCOUNT)));
+    }
+
+    #[test]
+    fn burn_rejects_a_caller_that_does_not_own_the_account() {
+        let mut ledger = Ledger::default();
+        ledger
+            .accounts
+            .insert(("alice".into(), DEFAULT_SUBACCOUNT), account("alice", DEFAULT_SUBACCOUNT, 50, 0));
+        ledger.total_supply = 50;
+
+        let result = burn_inner(&mut ledger, "mallory", "alice".into(), DEFAULT_SUBACCOUNT, 10, None, 0);
+
+        assert_eq!(result, Err("Caller is not authorized to burn this account".to_string()));
+        assert_eq!(ledger.accounts[&("alice".to_string(), DEFAULT_SUBACCOUNT)].balance, 50);
+        assert_eq!(ledger.total_supply, 50);
+    }
+
+    #[test]
+    fn burn_accepts_a_matching_caller_principal_for_a_principal_style_owner() {
+        let mut ledger = Ledger::default();
+        ledger
+            .accounts
+            .insert(("alice".into(), DEFAULT_SUBACCOUNT), account("alice", DEFAULT_SUBACCOUNT, 50, 0));
+        ledger.total_supply = 50;
+
+        burn_inner(&mut ledger, "alice", "alice".into(), DEFAULT_SUBACCOUNT, 20, None, 0).unwrap();
+
+        assert_eq!(ledger.accounts[&("alice".to_string(), DEFAULT_SUBACCOUNT)].balance, 30);
+        assert_eq!(ledger.total_supply, 30);
+    }
+
+    #[test]
+    fn burn_accepts_a_valid_mnemonic_index_proof_for_an_hd_derived_account() {
+        let id = derive_account_id("seed-phrase", 3);
+        let mut ledger = Ledger::default();
+        ledger
+            .accounts
+            .insert((id.clone(), DEFAULT_SUBACCOUNT), account(&id, DEFAULT_SUBACCOUNT, 50, 0));
+        ledger.total_supply = 50;
+
+        burn_inner(
+            &mut ledger,
+            "anyone", // no principal will ever equal an HD-derived id
+            id.clone(),
+            DEFAULT_SUBACCOUNT,
+            20,
+            Some(("seed-phrase".to_string(), 3)),
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(ledger.accounts[&(id, DEFAULT_SUBACCOUNT)].balance, 30);
+        assert_eq!(ledger.total_supply, 30);
+    }
+
+    #[test]
+    fn burn_rejects_a_proof_that_derives_to_a_different_account() {
+        let id = derive_account_id("seed-phrase", 3);
+        let mut ledger = Ledger::default();
+        ledger
+            .accounts
+            .insert((id.clone(), DEFAULT_SUBACCOUNT), account(&id, DEFAULT_SUBACCOUNT, 50, 0));
+        ledger.total_supply = 50;
+
+        let result = burn_inner(
+            &mut ledger,
+            "anyone",
+            id,
+            DEFAULT_SUBACCOUNT,
+            20,
+            Some(("wrong-seed-phrase".to_string(), 3)),
+            0,
+        );
+
+        assert_eq!(result, Err("Caller is not authorized to burn this account".to_string()));
+        assert_eq!(ledger.total_supply, 50);
+    }
+}